@@ -0,0 +1,90 @@
+pub enum TimeFormat {
+    Default,
+    Disabled,
+    Custom(String),
+}
+
+pub struct Config {
+    pub(crate) time_format: TimeFormat,
+    pub(crate) show_target: bool,
+    pub(crate) show_location: bool,
+    pub(crate) level_width: usize,
+    pub(crate) show_thread: bool,
+    pub(crate) thread_width: usize,
+}
+
+impl Config {
+    pub const fn new() -> Self {
+        Self {
+            time_format: TimeFormat::Default,
+            show_target: false,
+            show_location: false,
+            level_width: 3,
+            show_thread: true,
+            thread_width: 0,
+        }
+    }
+
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::new()
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct ConfigBuilder(Config);
+
+impl ConfigBuilder {
+    pub const fn new() -> Self {
+        Self(Config::new())
+    }
+
+    pub fn set_time_format(mut self, format: impl Into<String>) -> Self {
+        self.0.time_format = TimeFormat::Custom(format.into());
+        self
+    }
+
+    pub fn set_time_disabled(mut self) -> Self {
+        self.0.time_format = TimeFormat::Disabled;
+        self
+    }
+
+    pub fn set_target(mut self, show: bool) -> Self {
+        self.0.show_target = show;
+        self
+    }
+
+    pub fn set_location(mut self, show: bool) -> Self {
+        self.0.show_location = show;
+        self
+    }
+
+    pub fn set_level_width(mut self, width: usize) -> Self {
+        self.0.level_width = width;
+        self
+    }
+
+    pub fn set_thread(mut self, show: bool) -> Self {
+        self.0.show_thread = show;
+        self
+    }
+
+    pub fn set_thread_width(mut self, width: usize) -> Self {
+        self.0.thread_width = width;
+        self
+    }
+
+    pub fn build(self) -> Config {
+        self.0
+    }
+}
+
+impl Default for ConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
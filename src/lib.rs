@@ -1,7 +1,19 @@
+mod config;
+mod memory;
+mod rotate;
+
 use chrono::Utc;
 use lazy_exclusive::LazyExclusive;
-use log::{Level, Log};
-use std::io::Write;
+use log::{Level, LevelFilter, Log};
+use memory::MemoryStore;
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use config::TimeFormat;
+
+pub use config::{Config, ConfigBuilder};
+pub use memory::{RecentFilter, StoredRecord};
+pub use rotate::{rotating_file, RotatingFile};
 
 #[derive(Default)]
 pub enum Inner {
@@ -9,6 +21,7 @@ pub enum Inner {
     Stdout,
     Stderr,
     Buffer(Box<dyn Write>),
+    Multi(Vec<Inner>),
 }
 
 impl<T: Write + 'static> From<T> for Inner {
@@ -18,14 +31,76 @@ impl<T: Write + 'static> From<T> for Inner {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    const fn to_u8(self) -> u8 {
+        match self {
+            ColorMode::Auto => 0,
+            ColorMode::Always => 1,
+            ColorMode::Never => 2,
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => ColorMode::Always,
+            2 => ColorMode::Never,
+            _ => ColorMode::Auto,
+        }
+    }
+
+    fn applies(self, is_tty: bool) -> bool {
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => is_tty,
+        }
+    }
+}
+
+struct FilterConfig {
+    directives: Vec<(String, LevelFilter)>,
+    default: LevelFilter,
+}
+
+impl FilterConfig {
+    const fn new() -> Self {
+        Self {
+            directives: Vec::new(),
+            default: LevelFilter::Trace,
+        }
+    }
+}
+
+// A directive for target "a::b" must match "a::b" itself or a descendant
+// module ("a::b::c"), never an unrelated target that merely shares the
+// string prefix ("a::bc").
+fn target_matches(target: &str, prefix: &str) -> bool {
+    target == prefix || (target.starts_with(prefix) && target[prefix.len()..].starts_with("::"))
+}
+
 pub struct Logger {
     inner: LazyExclusive<Inner>,
+    filters: LazyExclusive<FilterConfig>,
+    memory: LazyExclusive<Option<MemoryStore>>,
+    config: LazyExclusive<Config>,
+    color: AtomicU8,
 }
 
 impl Default for Logger {
     fn default() -> Self {
         Self {
             inner: LazyExclusive::default(),
+            filters: LazyExclusive::new(FilterConfig::new()),
+            memory: LazyExclusive::default(),
+            config: LazyExclusive::default(),
+            color: AtomicU8::new(ColorMode::Auto.to_u8()),
         }
     }
 }
@@ -34,64 +109,234 @@ impl Logger {
     pub const fn new() -> Self {
         Self {
             inner: LazyExclusive::new(Inner::Stdout),
+            filters: LazyExclusive::new(FilterConfig::new()),
+            memory: LazyExclusive::new(None),
+            config: LazyExclusive::new(Config::new()),
+            color: AtomicU8::new(ColorMode::Auto.to_u8()),
         }
     }
 
     pub fn set_inner(&self, inner: Inner) {
         self.inner.swap(inner);
     }
+
+    pub fn set_config(&self, config: Config) {
+        *self.config.wait() = config;
+    }
+
+    pub fn set_color(&self, mode: ColorMode) {
+        self.color.store(mode.to_u8(), Ordering::Relaxed);
+    }
+
+    fn color_mode(&self) -> ColorMode {
+        ColorMode::from_u8(self.color.load(Ordering::Relaxed))
+    }
+
+    // Directives are `target=level` pairs separated by commas, with a bare
+    // level setting the default (e.g. "info,mycrate=debug,noisy=off"). The
+    // longest matching target prefix wins.
+    pub fn set_filter(&self, spec: &str) {
+        let mut directives = Vec::new();
+        let mut default = LevelFilter::Trace;
+
+        for directive in spec.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    if let Ok(filter) = level.trim().parse() {
+                        let target = target.trim().to_string();
+                        // A later directive for the same target overrides an
+                        // earlier one, matching RUST_LOG-style precedence.
+                        match directives.iter_mut().find(|(t, _)| *t == target) {
+                            Some(existing) => existing.1 = filter,
+                            None => directives.push((target, filter)),
+                        }
+                    }
+                }
+                None => {
+                    if let Ok(filter) = directive.parse() {
+                        default = filter;
+                    }
+                }
+            }
+        }
+
+        directives.sort_by_key(|(target, _)| std::cmp::Reverse(target.len()));
+
+        *self.filters.wait() = FilterConfig { directives, default };
+    }
+
+    // Keeps the last `capacity` records (or fewer, once `max_age` starts
+    // pruning them) in memory so callers can query them with `recent`
+    // instead of re-parsing a log file.
+    pub fn enable_memory(&self, capacity: usize, max_age: Option<chrono::Duration>) {
+        *self.memory.wait() = Some(MemoryStore::new(capacity, max_age));
+    }
+
+    pub fn recent(&self, filter: &RecentFilter) -> Vec<StoredRecord> {
+        match &mut *self.memory.wait() {
+            Some(store) => store.recent(filter),
+            None => Vec::new(),
+        }
+    }
 }
 
 impl Log for Logger {
-    fn enabled(&self, _metadata: &log::Metadata) -> bool {
-        true
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        let filters = self.filters.wait();
+        let target = metadata.target();
+
+        let filter = filters
+            .directives
+            .iter()
+            .find(|(prefix, _)| target_matches(target, prefix))
+            .map(|(_, level)| *level)
+            .unwrap_or(filters.default);
+
+        metadata.level() <= filter
     }
 
     fn flush(&self) {
         let mut lock = self.inner.wait();
-        match &mut *lock {
-            Inner::Buffer(buffer) => {
-                let _ = buffer.flush();
-            }
-            _ => (),
-        };
+        flush_inner(&mut lock);
     }
 
     fn log(&self, record: &log::Record) {
-        let mut lock = self.inner.wait();
-
-        const RESET_COLOR: &'static str = "\x1b[0m";
-        let (color, label) = match record.level() {
-            Level::Info => ("\x1B[97m", "INF"),
-            Level::Debug => ("\x1B[36m", "DBG"),
-            Level::Error => ("\x1B[31m", "ERR"),
-            Level::Warn => ("\x1B[33m", "WRN"),
-            Level::Trace => ("\x1B[97m", "TRC"),
-        };
+        if !self.enabled(record.metadata()) {
+            return;
+        }
 
         let current = std::thread::current();
         let name = current.name().unwrap_or("unknown");
 
-        match &mut *lock {
-            Inner::Stdout => {
-                println!(
-                    "{}{color} {name} [{label}]{RESET_COLOR} {}",
-                    Utc::now().format("%H:%M:%S"),
-                    record.args()
-                )
+        let line = format_line(&self.config.wait(), record, name);
+        write_record(&mut self.inner.wait(), record.level(), &line, self.color_mode());
+
+        if let Some(store) = &mut *self.memory.wait() {
+            store.push(StoredRecord {
+                timestamp: Utc::now(),
+                level: record.level(),
+                target: record.target().to_string(),
+                thread: name.to_string(),
+                message: record.args().to_string(),
+            });
+        }
+    }
+}
+
+fn flush_inner(inner: &mut Inner) {
+    match inner {
+        Inner::Buffer(buffer) => {
+            let _ = buffer.flush();
+        }
+        Inner::Multi(sinks) => {
+            for sink in sinks {
+                flush_inner(sink);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn level_label(level: Level) -> &'static str {
+    match level {
+        Level::Info => "INF",
+        Level::Debug => "DBG",
+        Level::Error => "ERR",
+        Level::Warn => "WRN",
+        Level::Trace => "TRC",
+    }
+}
+
+fn level_color(level: Level) -> &'static str {
+    match level {
+        Level::Info => "\x1B[97m",
+        Level::Debug => "\x1B[36m",
+        Level::Error => "\x1B[31m",
+        Level::Warn => "\x1B[33m",
+        Level::Trace => "\x1B[97m",
+    }
+}
+
+struct Line {
+    time: String,
+    // The part baseline colored: " {thread} [{label}]".
+    head: String,
+    // Target/location/message: always plain, even on a colored sink.
+    tail: String,
+}
+
+impl Line {
+    fn plain(&self) -> String {
+        format!("{}{}{}", self.time, self.head, self.tail)
+    }
+
+    fn colored(&self, level: Level) -> String {
+        const RESET_COLOR: &'static str = "\x1b[0m";
+        format!(
+            "{}{}{}{RESET_COLOR}{}",
+            self.time,
+            level_color(level),
+            self.head,
+            self.tail
+        )
+    }
+}
+
+fn format_line(config: &Config, record: &log::Record, thread: &str) -> Line {
+    let time = match &config.time_format {
+        TimeFormat::Disabled => String::new(),
+        TimeFormat::Default => Utc::now().format("%H:%M:%S").to_string(),
+        TimeFormat::Custom(format) => Utc::now().format(format).to_string(),
+    };
+
+    let mut head = String::new();
+    if config.show_thread {
+        head.push_str(&format!(" {thread:>width$}", width = config.thread_width));
+    }
+    head.push_str(&format!(
+        " [{:<width$}]",
+        level_label(record.level()),
+        width = config.level_width
+    ));
+
+    let mut tail = String::new();
+    if config.show_target {
+        tail.push(' ');
+        tail.push_str(record.target());
+    }
+    if config.show_location {
+        if let (Some(file), Some(line)) = (record.file(), record.line()) {
+            tail.push_str(&format!(" {file}:{line}"));
+        }
+    }
+    tail.push(' ');
+    tail.push_str(&record.args().to_string());
+
+    Line { time, head, tail }
+}
+
+fn write_record(inner: &mut Inner, level: Level, line: &Line, mode: ColorMode) {
+    match inner {
+        Inner::Stdout => {
+            if mode.applies(std::io::stdout().is_terminal()) {
+                println!("{}", line.colored(level))
+            } else {
+                println!("{}", line.plain())
             }
-            Inner::Stderr => eprintln!(
-                "{}{color} {name} [{label}] {RESET_COLOR} {}",
-                Utc::now().format("%H:%M:%S"),
-                record.args()
-            ),
-            Inner::Buffer(buffer) => {
-                let _ = writeln!(
-                    buffer,
-                    "{} {name} [{label}] {}",
-                    Utc::now().format("%H:%M:%S"),
-                    record.args()
-                );
+        }
+        Inner::Stderr => {
+            if mode.applies(std::io::stderr().is_terminal()) {
+                eprintln!("{}", line.colored(level))
+            } else {
+                eprintln!("{}", line.plain())
+            }
+        }
+        Inner::Buffer(buffer) => {
+            let _ = writeln!(buffer, "{}", line.plain());
+        }
+        Inner::Multi(sinks) => {
+            for sink in sinks {
+                write_record(sink, level, line, mode);
             }
         }
     }
@@ -110,6 +355,64 @@ where
     LOGGER.set_inner(inner.into());
 }
 
+pub fn fan_out(sinks: impl IntoIterator<Item = Inner>) -> Inner {
+    Inner::Multi(sinks.into_iter().collect())
+}
+
 pub fn init() -> Result<(), log::SetLoggerError> {
     log::set_logger(&LOGGER)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_matches_requires_module_boundary() {
+        assert!(target_matches("mycrate", "mycrate"));
+        assert!(target_matches("mycrate::module", "mycrate"));
+        assert!(!target_matches("mycrateXYZ", "mycrate"));
+        assert!(!target_matches("mycrate", "mycrate::module"));
+    }
+
+    fn filter_for(spec: &str, target: &str) -> LevelFilter {
+        let logger = Logger::default();
+        logger.set_filter(spec);
+        let filters = logger.filters.wait();
+        filters
+            .directives
+            .iter()
+            .find(|(prefix, _)| target_matches(target, prefix))
+            .map(|(_, level)| *level)
+            .unwrap_or(filters.default)
+    }
+
+    #[test]
+    fn set_filter_longest_prefix_wins() {
+        assert_eq!(
+            filter_for("info,mycrate=debug,mycrate::noisy=off", "mycrate::noisy"),
+            LevelFilter::Off
+        );
+        assert_eq!(
+            filter_for("info,mycrate=debug,mycrate::noisy=off", "mycrate::other"),
+            LevelFilter::Debug
+        );
+        assert_eq!(
+            filter_for("info,mycrate=debug,mycrate::noisy=off", "other"),
+            LevelFilter::Info
+        );
+    }
+
+    #[test]
+    fn set_filter_off_disables_target() {
+        assert_eq!(filter_for("info,noisy=off", "noisy"), LevelFilter::Off);
+    }
+
+    #[test]
+    fn set_filter_last_directive_for_target_wins() {
+        assert_eq!(
+            filter_for("info,mycrate=debug,mycrate=trace", "mycrate"),
+            LevelFilter::Trace
+        );
+    }
+}
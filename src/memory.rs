@@ -0,0 +1,216 @@
+use chrono::{DateTime, Duration, Utc};
+use log::Level;
+use regex::Regex;
+use std::collections::VecDeque;
+
+#[derive(Clone)]
+pub struct StoredRecord {
+    pub timestamp: DateTime<Utc>,
+    pub level: Level,
+    pub target: String,
+    pub thread: String,
+    pub message: String,
+}
+
+#[derive(Default)]
+pub struct RecentFilter<'a> {
+    pub min_level: Option<Level>,
+    pub target: Option<&'a str>,
+    pub message: Option<&'a Regex>,
+    pub not_before: Option<DateTime<Utc>>,
+    pub limit: Option<usize>,
+}
+
+impl RecentFilter<'_> {
+    fn matches(&self, record: &StoredRecord) -> bool {
+        if let Some(min_level) = self.min_level {
+            if record.level > min_level {
+                return false;
+            }
+        }
+
+        if let Some(target) = self.target {
+            if !record.target.contains(target) {
+                return false;
+            }
+        }
+
+        if let Some(not_before) = self.not_before {
+            if record.timestamp < not_before {
+                return false;
+            }
+        }
+
+        if let Some(message) = self.message {
+            if !message.is_match(&record.message) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+pub(crate) struct MemoryStore {
+    records: VecDeque<StoredRecord>,
+    capacity: usize,
+    max_age: Option<Duration>,
+}
+
+impl MemoryStore {
+    pub(crate) fn new(capacity: usize, max_age: Option<Duration>) -> Self {
+        Self {
+            records: VecDeque::new(),
+            capacity,
+            max_age,
+        }
+    }
+
+    pub(crate) fn push(&mut self, record: StoredRecord) {
+        self.records.push_back(record);
+
+        while self.records.len() > self.capacity {
+            self.records.pop_front();
+        }
+
+        self.prune();
+    }
+
+    // Called from push() and recent() rather than on a background task, so
+    // age-based eviction only happens in step with logging/querying activity.
+    fn prune(&mut self) {
+        let Some(max_age) = self.max_age else {
+            return;
+        };
+
+        let cutoff = Utc::now() - max_age;
+        while self.records.front().is_some_and(|r| r.timestamp < cutoff) {
+            self.records.pop_front();
+        }
+    }
+
+    pub(crate) fn recent(&mut self, filter: &RecentFilter) -> Vec<StoredRecord> {
+        self.prune();
+
+        let mut matched: Vec<StoredRecord> = self
+            .records
+            .iter()
+            .rev()
+            .filter(|record| filter.matches(record))
+            .cloned()
+            .collect();
+
+        if let Some(limit) = filter.limit {
+            matched.truncate(limit);
+        }
+
+        matched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(level: Level, target: &str, message: &str) -> StoredRecord {
+        StoredRecord {
+            timestamp: Utc::now(),
+            level,
+            target: target.to_string(),
+            thread: "main".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn push_evicts_oldest_past_capacity() {
+        let mut store = MemoryStore::new(2, None);
+        store.push(record(Level::Info, "a", "one"));
+        store.push(record(Level::Info, "a", "two"));
+        store.push(record(Level::Info, "a", "three"));
+
+        let kept = store.recent(&RecentFilter::default());
+        let messages: Vec<&str> = kept.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, vec!["three", "two"]);
+    }
+
+    #[test]
+    fn prune_evicts_records_older_than_max_age() {
+        let mut store = MemoryStore::new(10, Some(Duration::seconds(-1)));
+        store.push(record(Level::Info, "a", "one"));
+
+        assert!(store.recent(&RecentFilter::default()).is_empty());
+    }
+
+    #[test]
+    fn recent_filters_by_min_level() {
+        let mut store = MemoryStore::new(10, None);
+        store.push(record(Level::Debug, "a", "debug"));
+        store.push(record(Level::Error, "a", "error"));
+
+        let filter = RecentFilter {
+            min_level: Some(Level::Warn),
+            ..Default::default()
+        };
+        let kept = store.recent(&filter);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].message, "error");
+    }
+
+    #[test]
+    fn recent_filters_by_target_substring() {
+        let mut store = MemoryStore::new(10, None);
+        store.push(record(Level::Info, "mycrate::module", "in module"));
+        store.push(record(Level::Info, "other", "elsewhere"));
+
+        let filter = RecentFilter {
+            target: Some("module"),
+            ..Default::default()
+        };
+        let kept = store.recent(&filter);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].message, "in module");
+    }
+
+    #[test]
+    fn recent_filters_by_message_regex() {
+        let mut store = MemoryStore::new(10, None);
+        store.push(record(Level::Info, "a", "connection failed"));
+        store.push(record(Level::Info, "a", "connection ok"));
+
+        let re = Regex::new("failed$").unwrap();
+        let filter = RecentFilter {
+            message: Some(&re),
+            ..Default::default()
+        };
+        let kept = store.recent(&filter);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].message, "connection failed");
+    }
+
+    #[test]
+    fn recent_filters_by_not_before() {
+        let mut store = MemoryStore::new(10, None);
+        store.push(record(Level::Info, "a", "old"));
+
+        let filter = RecentFilter {
+            not_before: Some(Utc::now() + Duration::seconds(60)),
+            ..Default::default()
+        };
+        assert!(store.recent(&filter).is_empty());
+    }
+
+    #[test]
+    fn recent_respects_limit() {
+        let mut store = MemoryStore::new(10, None);
+        store.push(record(Level::Info, "a", "one"));
+        store.push(record(Level::Info, "a", "two"));
+        store.push(record(Level::Info, "a", "three"));
+
+        let filter = RecentFilter {
+            limit: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(store.recent(&filter).len(), 2);
+    }
+}
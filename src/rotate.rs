@@ -0,0 +1,184 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+pub struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    max_bytes: u64,
+    written: u64,
+    keep: usize,
+    // Buffers a partial line until its `\n` is seen, so a line never gets
+    // split across a rotation boundary even when the caller's `writeln!`
+    // issues separate `write()` calls for the content and the trailing `\n`.
+    // A record whose message contains newlines is written line by line, and
+    // a rotation can fall between those lines.
+    pending: Vec<u8>,
+}
+
+impl RotatingFile {
+    pub fn new(path: impl Into<PathBuf>, max_bytes: u64, keep: usize) -> io::Result<Self> {
+        let path = path.into();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let written = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            file,
+            max_bytes,
+            written,
+            keep,
+            pending: Vec::new(),
+        })
+    }
+
+    fn write_record(&mut self, record: &[u8]) -> io::Result<()> {
+        if self.written + record.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+
+        self.file.write_all(record)?;
+        self.written += record.len() as u64;
+        Ok(())
+    }
+
+    fn archive_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        for index in (1..self.keep).rev() {
+            let from = self.archive_path(index);
+            if from.exists() {
+                let _ = std::fs::rename(from, self.archive_path(index + 1));
+            }
+        }
+
+        if self.keep > 0 {
+            let _ = std::fs::rename(&self.path, self.archive_path(1));
+        }
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.written = 0;
+
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+
+        while let Some(pos) = self.pending.iter().position(|&b| b == b'\n') {
+            let record: Vec<u8> = self.pending.drain(..=pos).collect();
+            self.write_record(&record)?;
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            let record = std::mem::take(&mut self.pending);
+            self.write_record(&record)?;
+        }
+
+        self.file.flush()
+    }
+}
+
+pub fn rotating_file(
+    path: impl Into<PathBuf>,
+    max_bytes: u64,
+    keep: usize,
+) -> io::Result<RotatingFile> {
+    RotatingFile::new(path, max_bytes, keep)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn temp_path(name: &str) -> PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("logger-rotate-test-{name}-{n}.log"))
+    }
+
+    fn cleanup(path: &PathBuf, keep: usize) {
+        let _ = std::fs::remove_file(path);
+        for index in 1..=keep {
+            let mut name = path.clone().into_os_string();
+            name.push(format!(".{index}"));
+            let _ = std::fs::remove_file(PathBuf::from(name));
+        }
+    }
+
+    #[test]
+    fn records_split_across_writes_are_not_split_across_rotation() {
+        let path = temp_path("split");
+        let mut file = RotatingFile::new(&path, 16, 2).unwrap();
+
+        // Simulate `writeln!`, which issues one `write()` for the content
+        // and a separate one for the trailing `\n`.
+        for record in ["aaaaaaaa", "bbbbbbbb", "cccccccc"] {
+            file.write_all(record.as_bytes()).unwrap();
+            file.write_all(b"\n").unwrap();
+        }
+        file.flush().unwrap();
+
+        let current = std::fs::read_to_string(&path).unwrap();
+        for line in current.lines() {
+            assert!(!line.is_empty() && line.chars().all(|c| c == line.chars().next().unwrap()));
+        }
+
+        let archived = std::fs::read_to_string(path.with_extension("log.1")).unwrap_or_default();
+        for line in archived.lines() {
+            assert!(line.len() == 8, "line split across rotation: {line:?}");
+        }
+
+        cleanup(&path, 2);
+    }
+
+    #[test]
+    fn archive_count_never_exceeds_keep() {
+        let path = temp_path("keep");
+        let mut file = RotatingFile::new(&path, 8, 2).unwrap();
+
+        for record in ["one\n", "two\n", "three\n", "four\n", "five\n"] {
+            file.write_all(record.as_bytes()).unwrap();
+        }
+        file.flush().unwrap();
+
+        let mut name = path.clone().into_os_string();
+        name.push(".3");
+        assert!(!PathBuf::from(name).exists());
+
+        cleanup(&path, 2);
+    }
+
+    #[test]
+    fn keep_zero_rotates_without_archiving() {
+        let path = temp_path("keep0");
+        let mut file = RotatingFile::new(&path, 4, 0).unwrap();
+
+        file.write_all(b"aaaa\n").unwrap();
+        file.write_all(b"bbbb\n").unwrap();
+        file.flush().unwrap();
+
+        let mut name = path.clone().into_os_string();
+        name.push(".1");
+        assert!(!PathBuf::from(name).exists());
+
+        cleanup(&path, 0);
+    }
+}